@@ -0,0 +1,124 @@
+//! A BK-tree over the indexed vocabulary, for fuzzy-correcting query terms
+//! that don't appear in the index. Each node is a term; an edge to a
+//! child is labeled with the Levenshtein distance from parent to child.
+//! A lookup for terms within `k` of a query only recurses into children
+//! whose edge label lies in `[d-k, d+k]`, where `d` is the distance from
+//! the current node to the query (triangle-inequality pruning).
+
+use std::collections::BTreeMap;
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+
+    dp[a.len()][b.len()]
+}
+
+struct Node {
+    term: String,
+    children: BTreeMap<usize, Box<Node>>,
+}
+
+#[derive(Default)]
+pub struct BkTree {
+    root: Option<Box<Node>>,
+}
+
+impl BkTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, term: String) {
+        let Some(root) = &mut self.root else {
+            self.root = Some(Box::new(Node {
+                term,
+                children: BTreeMap::new(),
+            }));
+            return;
+        };
+
+        Self::insert_node(root, term);
+    }
+
+    fn insert_node(node: &mut Node, term: String) {
+        let d = levenshtein(&node.term, &term);
+        if d == 0 {
+            return;
+        }
+
+        match node.children.get_mut(&d) {
+            Some(child) => Self::insert_node(child, term),
+            None => {
+                node.children.insert(
+                    d,
+                    Box::new(Node {
+                        term,
+                        children: BTreeMap::new(),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// Every indexed term within edit distance `k` of `query`, each paired
+    /// with its distance.
+    pub fn find_within(&self, query: &str, k: usize) -> Vec<(&str, usize)> {
+        let mut results = Vec::new();
+        if let Some(root) = &self.root {
+            Self::search_node(root, query, k, &mut results);
+        }
+        results
+    }
+
+    fn search_node<'a>(node: &'a Node, query: &str, k: usize, results: &mut Vec<(&'a str, usize)>) {
+        let d = levenshtein(&node.term, query);
+        if d <= k {
+            results.push((&node.term, d));
+        }
+
+        let lo = d.saturating_sub(k);
+        let hi = d + k;
+        for (&edge, child) in &node.children {
+            if edge >= lo && edge <= hi {
+                Self::search_node(child, query, k, results);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_close_terms_and_prunes_far_ones() {
+        let mut tree = BkTree::new();
+        for term in ["BOOK", "BOOKS", "CAKE", "CAKES", "BOAK", "CAPE"] {
+            tree.insert(term.to_string());
+        }
+
+        let mut matches = tree.find_within("BOOK", 1);
+        matches.sort();
+        assert_eq!(matches, vec![("BOAK", 1), ("BOOK", 0), ("BOOKS", 1)]);
+
+        assert!(tree.find_within("BOOK", 0).contains(&("BOOK", 0)));
+    }
+}
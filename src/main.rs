@@ -5,40 +5,16 @@ use std::{
     path::Path,
     process::ExitCode,
 };
-use xml::{
-    common::{Position, TextPosition},
-    reader::{EventReader, XmlEvent},
-};
 
+mod bktree;
 mod model;
+mod parsers;
 mod server;
+mod stemmer;
 use model::*;
+use parsers::parse_file;
 
-fn parse_xml_file(file_path: &Path) -> Result<String, ()> {
-    let file = File::open(file_path)
-        .map_err(|err| eprintln!("ERROR: could not open file {}: {err}", file_path.display()))?;
-
-    let mut content = String::new();
-
-    for event in EventReader::new(BufReader::new(file)).into_iter() {
-        let event = event.map_err(|err| {
-            let TextPosition { row, column } = err.position();
-            let msg = err.msg();
-            eprintln!(
-                "{file_path}:{row}:{column}: ERROR: {msg}",
-                file_path = file_path.display()
-            )
-        })?;
-
-        if let XmlEvent::Characters(text) = event {
-            content.push_str(&text);
-            content.push(' ');
-        }
-    }
-    Ok(content)
-}
-
-fn tfi_folder(dir_path: &Path, tfi: &mut TermFreqIndex) -> Result<(), ()> {
+fn tfi_folder(dir_path: &Path, tfi: &mut TermFreqIndex, pipeline: Pipeline) -> Result<(), ()> {
     let dir = fs::read_dir(dir_path).map_err(|err| {
         eprintln!(
             "ERROR: could not open directory {} for indexing: {err}",
@@ -74,13 +50,13 @@ fn tfi_folder(dir_path: &Path, tfi: &mut TermFreqIndex) -> Result<(), ()> {
         })?;
 
         if file_type.is_dir() {
-            tfi_folder(&file_path, tfi)?;
+            tfi_folder(&file_path, tfi, pipeline)?;
             continue 'next_file;
         }
 
         println!("Indexing {:?}", &file_path);
 
-        let content = match parse_xml_file(&file_path) {
+        let content = match parse_file(&file_path) {
             Ok(content) => content.chars().collect::<Vec<_>>(),
             Err(()) => continue 'next_file,
         };
@@ -88,6 +64,10 @@ fn tfi_folder(dir_path: &Path, tfi: &mut TermFreqIndex) -> Result<(), ()> {
         let mut tf: TermFreq = TermFreq::new();
 
         for term in Lexer::new(&content) {
+            let Some(term) = pipeline.normalize(term) else {
+                continue;
+            };
+
             if let Some(freq) = tf.get_mut(&term) {
                 *freq += 1;
             } else {
@@ -104,24 +84,59 @@ fn tfi_folder(dir_path: &Path, tfi: &mut TermFreqIndex) -> Result<(), ()> {
     Ok(())
 }
 
-fn save_tfi(tfi: &TermFreqIndex, index_path: &str) -> Result<(), ()> {
+/// Index files are MessagePack by default; a `.json` extension opts into
+/// the pretty-printed JSON form for inspection.
+fn is_json_path(index_path: &str) -> bool {
+    Path::new(index_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn save_tfi(index: &InvertedIndex, index_path: &str) -> Result<(), ()> {
     println!("Saving {}", index_path);
 
     let index_file = File::create(index_path)
         .map_err(|err| eprintln!("ERROR: could not create index file {index_path}: {err}"))?;
 
-    serde_json::to_writer_pretty(BufWriter::new(index_file), &tfi).map_err(|err| {
-        eprintln!("ERROR: could not serialize index into file {index_path}: {err}")
-    })?;
+    let mut writer = BufWriter::new(index_file);
+
+    if is_json_path(index_path) {
+        serde_json::to_writer_pretty(writer, index).map_err(|err| {
+            eprintln!("ERROR: could not serialize index into file {index_path}: {err}")
+        })
+    } else {
+        rmp_serde::encode::write(&mut writer, index).map_err(|err| {
+            eprintln!("ERROR: could not serialize index into file {index_path}: {err}")
+        })
+    }?;
 
     Ok(())
 }
 
+fn load_tfi(index_path: &str) -> Result<InvertedIndex, ()> {
+    let index_file = File::open(index_path)
+        .map_err(|err| eprintln!("ERROR: could not open index file {index_path}: {err}"))?;
+
+    let mut reader = BufReader::new(index_file);
+
+    if is_json_path(index_path) {
+        serde_json::from_reader(reader)
+            .map_err(|err| eprintln!("ERROR: could not parse index file {index_path}: {err}"))
+    } else {
+        rmp_serde::from_read(&mut reader)
+            .map_err(|err| eprintln!("ERROR: could not parse index file {index_path}: {err}"))
+    }
+}
+
 fn usage(program: &str) {
     eprintln!("Usage: {program} [SUBCOMMAND] [OPTIONS]");
     eprintln!("Subcommandes:");
     eprintln!(
-        "     index  <folder>                index the <folder> and save the index to index.json"
+        "     index  <folder> [--no-stem] [--json]  index the <folder> and save the index to"
+    );
+    eprintln!("                                            index.mpk, or index.json with --json");
+    eprintln!(
+        "                                            --no-stem disables stopword filtering and stemming"
     );
     eprintln!("     search <index-file> <query>     search <query> within the <index-file>");
     eprintln!("     serve  <index-file> [address]  start the local HTTP server with web interface")
@@ -143,9 +158,26 @@ fn entry() -> Result<(), ()> {
                 eprintln!("ERROR: no dir is provided for {subcommand} subcommand")
             })?;
 
+            let rest: Vec<String> = args.collect();
+            let no_stem = rest.iter().any(|arg| arg == "--no-stem");
+            let json_output = rest.iter().any(|arg| arg == "--json");
+            let pipeline = Pipeline { stemming: !no_stem };
+
             let mut tfi: TermFreqIndex = TermFreqIndex::new();
-            tfi_folder(Path::new(&dir_path), &mut tfi)?;
-            save_tfi(&tfi, "index.json")
+            tfi_folder(Path::new(&dir_path), &mut tfi, pipeline)?;
+
+            let mut index = InvertedIndex::new(pipeline);
+            for (path, tf) in tfi {
+                index.add_document(path, &tf);
+            }
+            index.recompute_avgdl();
+
+            let index_path = if json_output {
+                "index.json"
+            } else {
+                "index.mpk"
+            };
+            save_tfi(&index, index_path)
         }
         "search" => {
             let index_path = args.next().ok_or_else(|| {
@@ -162,16 +194,16 @@ fn entry() -> Result<(), ()> {
                 .chars()
                 .collect::<Vec<_>>();
 
-            let index_file = File::open(&index_path).map_err(|err| {
-                eprintln!("ERROR: could not open index file {index_path}: {err}");
-            })?;
+            let index = load_tfi(&index_path)?;
+
+            let bktree = index.build_bktree();
+            let results = index.search_query(&prompt, &bktree);
 
-            let tf_index: TermFreqIndex = serde_json::from_reader(BufReader::new(index_file))
-                .map_err(|err| {
-                    eprintln!("ERROR: could not parse index file {index_path}: {err}");
-                })?;
+            for (term, corrected) in &results.corrections {
+                println!("Did you mean \"{corrected}\" instead of \"{term}\"?");
+            }
 
-            for (path, rank) in search_query(&tf_index, &prompt).iter().take(20) {
+            for (path, rank) in results.hits.iter().take(20) {
                 println!("{path} {rank}", path = path.display());
             }
 
@@ -183,16 +215,10 @@ fn entry() -> Result<(), ()> {
                 eprintln!("ERROR: no path to index is provided for {subcommand} subcommand")
             })?;
 
-            let index_file = File::open(&index_path).map_err(|err| {
-                eprintln!("ERROR: could not open index file {}: {err}", index_path)
-            })?;
-
-            let tf_index: TermFreqIndex = serde_json::from_reader(index_file).map_err(|err| {
-                eprintln!("ERROR: could not parse index file {index_path}: {err}")
-            })?;
+            let index = load_tfi(&index_path)?;
 
             let address: String = args.next().unwrap_or("127.0.0.1:6969".to_string());
-            server::start(&address, &tf_index)
+            server::start(&address, &index)
         }
         _ => {
             usage(&program);
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bktree::BkTree;
+
+pub type TermFreq = HashMap<String, usize>;
+pub type TermFreqIndex = HashMap<PathBuf, TermFreq>;
+
+pub struct Lexer<'a> {
+    content: &'a [char],
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(content: &'a [char]) -> Self {
+        Self { content }
+    }
+
+    fn trim_left(&mut self) {
+        while !self.content.is_empty() && self.content[0].is_whitespace() {
+            self.content = &self.content[1..];
+        }
+    }
+
+    fn chop(&mut self, n: usize) -> &'a [char] {
+        let token = &self.content[0..n];
+        self.content = &self.content[n..];
+        token
+    }
+
+    fn chop_while<P>(&mut self, mut predicate: P) -> &'a [char]
+    where
+        P: FnMut(&char) -> bool,
+    {
+        let mut n = 0;
+        while n < self.content.len() && predicate(&self.content[n]) {
+            n += 1;
+        }
+        self.chop(n)
+    }
+
+    pub fn next_token(&mut self) -> Option<String> {
+        self.trim_left();
+
+        if self.content.is_empty() {
+            return None;
+        }
+
+        if self.content[0].is_numeric() {
+            return Some(self.chop_while(|x| x.is_numeric()).iter().collect());
+        }
+
+        if self.content[0].is_alphabetic() {
+            return Some(
+                self.chop_while(|x| x.is_alphanumeric())
+                    .iter()
+                    .map(|x| x.to_ascii_uppercase())
+                    .collect(),
+            );
+        }
+
+        Some(self.chop(1).iter().collect())
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+/// Common English function words dropped before stemming; kept uppercase
+/// to match [`Lexer`]'s case-folded tokens.
+const STOPWORDS: &[&str] = &[
+    "A", "AN", "AND", "ARE", "AS", "AT", "BE", "BUT", "BY", "FOR", "FROM", "HAD", "HAS", "HAVE",
+    "HE", "IN", "IS", "IT", "ITS", "NOT", "OF", "ON", "OR", "THAT", "THE", "THIS", "TO", "WAS",
+    "WERE", "WILL", "WITH",
+];
+
+fn is_stopword(term: &str) -> bool {
+    STOPWORDS.contains(&term)
+}
+
+/// The text-normalization pipeline applied to both indexed and query
+/// terms. Stored alongside the index it was built with so `search`/`serve`
+/// always mirror the pipeline used at `index` time.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub stemming: bool,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self { stemming: true }
+    }
+}
+
+impl Pipeline {
+    /// Drops stopwords and stems the remaining tokens. Returns `None` for
+    /// a dropped stopword. A no-op when stemming is disabled.
+    pub fn normalize(&self, token: String) -> Option<String> {
+        if !self.stemming {
+            return Some(token);
+        }
+
+        if is_stopword(&token) {
+            return None;
+        }
+
+        Some(crate::stemmer::stem(&token))
+    }
+}
+
+pub type DocId = u32;
+pub type TermId = u32;
+
+/// BM25 term-frequency saturation parameter.
+const BM25_K1: f32 = 1.2;
+/// BM25 document-length normalization parameter.
+const BM25_B: f32 = 0.75;
+
+/// An inverted index over an interned vocabulary: `term_id -> postings`,
+/// where each posting is `(DocId, freq)`. Built from the forward
+/// [`TermFreqIndex`] produced while walking a folder, and queried with
+/// BM25 ranking instead of a per-query scan of every document.
+#[derive(Serialize, Deserialize)]
+pub struct InvertedIndex {
+    docs: Vec<PathBuf>,
+    doc_len: Vec<u32>,
+    avgdl: f32,
+    vocab: HashMap<String, TermId>,
+    terms: Vec<String>,
+    postings: Vec<Vec<(DocId, u32)>>,
+    pipeline: Pipeline,
+}
+
+impl InvertedIndex {
+    pub fn new(pipeline: Pipeline) -> Self {
+        Self {
+            docs: Vec::new(),
+            doc_len: Vec::new(),
+            avgdl: 0.0,
+            vocab: HashMap::new(),
+            terms: Vec::new(),
+            postings: Vec::new(),
+            pipeline,
+        }
+    }
+
+    pub fn doc_path(&self, doc_id: DocId) -> &Path {
+        &self.docs[doc_id as usize]
+    }
+
+    pub fn term_id(&self, term: &str) -> Option<TermId> {
+        self.vocab.get(term).copied()
+    }
+
+    pub fn df(&self, term_id: TermId) -> usize {
+        self.postings[term_id as usize].len()
+    }
+
+    fn intern(&mut self, term: &str) -> TermId {
+        if let Some(&term_id) = self.vocab.get(term) {
+            return term_id;
+        }
+
+        let term_id = self.terms.len() as TermId;
+        self.terms.push(term.to_string());
+        self.postings.push(Vec::new());
+        self.vocab.insert(term.to_string(), term_id);
+        term_id
+    }
+
+    /// Adds a document and its term frequencies to the postings lists.
+    /// `avgdl` is not updated here; call [`InvertedIndex::recompute_avgdl`]
+    /// once after every document has been added.
+    pub fn add_document(&mut self, path: PathBuf, tf: &TermFreq) {
+        let doc_id = self.docs.len() as DocId;
+        self.docs.push(path);
+        self.doc_len.push(tf.values().sum::<usize>() as u32);
+
+        for (term, &freq) in tf {
+            let term_id = self.intern(term);
+            self.postings[term_id as usize].push((doc_id, freq as u32));
+        }
+    }
+
+    pub fn recompute_avgdl(&mut self) {
+        let total: u64 = self.doc_len.iter().map(|&len| len as u64).sum();
+        self.avgdl = if self.docs.is_empty() {
+            0.0
+        } else {
+            total as f32 / self.docs.len() as f32
+        };
+    }
+
+    fn idf(&self, term_id: TermId) -> f32 {
+        let n = self.docs.len() as f32;
+        let df = self.df(term_id) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    fn bm25(&self, term_id: TermId, doc_id: DocId, freq: u32) -> f32 {
+        let f = freq as f32;
+        let dl = self.doc_len[doc_id as usize] as f32;
+        let denom = f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / self.avgdl);
+        self.idf(term_id) * (f * (BM25_K1 + 1.0)) / denom
+    }
+
+    /// Builds a BK-tree over the interned vocabulary, for correcting
+    /// query terms that aren't in the index.
+    pub fn build_bktree(&self) -> BkTree {
+        let mut tree = BkTree::new();
+        for term in &self.terms {
+            tree.insert(term.clone());
+        }
+        tree
+    }
+
+    /// Finds the closest vocabulary term to `term` within edit distance 1,
+    /// falling back to distance 2. Ties are broken by document frequency
+    /// (the more common spelling is the more likely correction), then by
+    /// the candidate term itself so the result is deterministic.
+    fn correct_term(&self, bktree: &BkTree, term: &str) -> Option<String> {
+        [1, 2].into_iter().find_map(|k| {
+            bktree
+                .find_within(term, k)
+                .into_iter()
+                .max_by_key(|(candidate, _)| {
+                    let df = self.term_id(candidate).map(|id| self.df(id)).unwrap_or(0);
+                    (df, std::cmp::Reverse(*candidate))
+                })
+                .map(|(candidate, _)| candidate.to_string())
+        })
+    }
+
+    /// Unions the postings lists of every query term and scores each
+    /// candidate document with BM25, highest score first. Query terms
+    /// missing from the index are fuzzy-corrected against `bktree` and
+    /// reported back as `(original, corrected)` pairs.
+    pub fn search_query(&self, query: &[char], bktree: &BkTree) -> SearchResults<'_> {
+        let mut corrections = Vec::new();
+        let tokens = Lexer::new(query)
+            .filter_map(|token| self.pipeline.normalize(token))
+            .filter_map(|token| {
+                if self.term_id(&token).is_some() {
+                    return Some(token);
+                }
+
+                let corrected = self.correct_term(bktree, &token)?;
+                corrections.push((token, corrected.clone()));
+                Some(corrected)
+            })
+            .collect::<Vec<_>>();
+
+        let mut scores: HashMap<DocId, f32> = HashMap::new();
+        for token in &tokens {
+            let Some(term_id) = self.term_id(token) else {
+                continue;
+            };
+
+            for &(doc_id, freq) in &self.postings[term_id as usize] {
+                *scores.entry(doc_id).or_insert(0.0) += self.bm25(term_id, doc_id, freq);
+            }
+        }
+
+        let mut hits: Vec<(&Path, f32)> = scores
+            .into_iter()
+            .map(|(doc_id, score)| (self.doc_path(doc_id), score))
+            .collect();
+
+        hits.sort_by(|(_, rank1), (_, rank2)| rank2.partial_cmp(rank1).unwrap());
+
+        SearchResults { hits, corrections }
+    }
+}
+
+/// Result of [`InvertedIndex::search_query`]: the ranked hits plus any
+/// "did you mean" corrections applied to unknown query terms.
+pub struct SearchResults<'a> {
+    pub hits: Vec<(&'a Path, f32)>,
+    pub corrections: Vec<(String, String)>,
+}
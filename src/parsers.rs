@@ -0,0 +1,190 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use serde_json::Value;
+use xml::{
+    common::{Position, TextPosition},
+    reader::{EventReader, XmlEvent},
+};
+
+fn parse_xml_file(file_path: &Path) -> Result<String, ()> {
+    let file = File::open(file_path)
+        .map_err(|err| eprintln!("ERROR: could not open file {}: {err}", file_path.display()))?;
+
+    let mut content = String::new();
+
+    for event in EventReader::new(BufReader::new(file)).into_iter() {
+        let event = event.map_err(|err| {
+            let TextPosition { row, column } = err.position();
+            let msg = err.msg();
+            eprintln!(
+                "{file_path}:{row}:{column}: ERROR: {msg}",
+                file_path = file_path.display()
+            )
+        })?;
+
+        if let XmlEvent::Characters(text) = event {
+            content.push_str(&text);
+            content.push(' ');
+        }
+    }
+    Ok(content)
+}
+
+fn parse_plaintext_file(file_path: &Path) -> Result<String, ()> {
+    read_to_string(file_path)
+}
+
+/// Strips tags from HTML, keeping the text between them. Not a real HTML
+/// parser: it tracks whether it is inside a `< ... >` tag and otherwise
+/// copies characters through, decoding the handful of entities that show
+/// up in ordinary prose.
+fn parse_html_file(file_path: &Path) -> Result<String, ()> {
+    let raw = read_to_string(file_path)?;
+
+    let mut content = String::new();
+    let mut in_tag = false;
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            '&' if !in_tag => {
+                let mut entity = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == ';' {
+                        chars.next();
+                        break;
+                    }
+                    if next.is_whitespace() || entity.len() > 8 {
+                        break;
+                    }
+                    entity.push(next);
+                    chars.next();
+                }
+                content.push_str(match entity.as_str() {
+                    "amp" => "&",
+                    "lt" => "<",
+                    "gt" => ">",
+                    "quot" => "\"",
+                    "apos" | "#39" => "'",
+                    _ => " ",
+                });
+                content.push(' ');
+            }
+            _ if !in_tag => content.push(c),
+            _ => {}
+        }
+    }
+
+    Ok(content)
+}
+
+/// Splits a CSV line into fields, honouring double-quoted fields (which
+/// may contain commas) and the `""` escape for a literal quote.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+            }
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+fn parse_csv_file(file_path: &Path) -> Result<String, ()> {
+    let raw = read_to_string(file_path)?;
+
+    let mut content = String::new();
+    for line in raw.lines() {
+        for field in split_csv_line(line) {
+            content.push_str(field.trim());
+            content.push(' ');
+        }
+    }
+
+    Ok(content)
+}
+
+fn parse_json_file(file_path: &Path) -> Result<String, ()> {
+    let raw = read_to_string(file_path)?;
+
+    let value: Value = serde_json::from_str(&raw).map_err(|err| {
+        eprintln!(
+            "ERROR: could not parse JSON file {}: {err}",
+            file_path.display()
+        )
+    })?;
+
+    let mut content = String::new();
+    collect_json_strings(&value, &mut content);
+    Ok(content)
+}
+
+fn collect_json_strings(value: &Value, content: &mut String) {
+    match value {
+        Value::String(s) => {
+            content.push_str(s);
+            content.push(' ');
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_json_strings(item, content);
+            }
+        }
+        Value::Object(fields) => {
+            for item in fields.values() {
+                collect_json_strings(item, content);
+            }
+        }
+        Value::Number(_) | Value::Bool(_) | Value::Null => {}
+    }
+}
+
+fn read_to_string(file_path: &Path) -> Result<String, ()> {
+    let mut file = File::open(file_path)
+        .map_err(|err| eprintln!("ERROR: could not open file {}: {err}", file_path.display()))?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content).map_err(|err| {
+        eprintln!(
+            "ERROR: could not read file {} as text: {err}",
+            file_path.display()
+        )
+    })?;
+
+    Ok(content)
+}
+
+/// Extracts the indexable text out of `file_path`, dispatching on its
+/// extension. Unrecognized extensions are read as plaintext.
+pub fn parse_file(file_path: &Path) -> Result<String, ()> {
+    match file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("xml") => parse_xml_file(file_path),
+        Some("html") | Some("htm") => parse_html_file(file_path),
+        Some("csv") => parse_csv_file(file_path),
+        Some("json") => parse_json_file(file_path),
+        _ => parse_plaintext_file(file_path),
+    }
+}
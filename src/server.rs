@@ -0,0 +1,134 @@
+use std::fs::File;
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::bktree::BkTree;
+use crate::model::*;
+
+#[derive(Serialize)]
+struct SearchResultEntry {
+    path: String,
+    score: f32,
+}
+
+#[derive(Serialize)]
+struct CorrectionEntry {
+    term: String,
+    corrected: String,
+}
+
+#[derive(Serialize)]
+struct SearchResponse {
+    results: Vec<SearchResultEntry>,
+    corrections: Vec<CorrectionEntry>,
+}
+
+fn serve_404(request: Request) -> Result<(), ()> {
+    request
+        .respond(Response::from_string("404").with_status_code(404))
+        .map_err(|err| eprintln!("ERROR: could not serve request: {err}"))
+}
+
+fn serve_500(request: Request) -> Result<(), ()> {
+    request
+        .respond(Response::from_string("500").with_status_code(500))
+        .map_err(|err| eprintln!("ERROR: could not serve request: {err}"))
+}
+
+fn serve_static_file(request: Request, file_path: &str, content_type: &str) -> Result<(), ()> {
+    let file = match File::open(file_path) {
+        Ok(file) => file,
+        Err(err) => {
+            eprintln!("ERROR: could not serve file {file_path}: {err}");
+            return serve_500(request);
+        }
+    };
+
+    let header = Header::from_bytes("Content-Type", content_type)
+        .expect("static content-type value is always a valid header");
+
+    request
+        .respond(Response::from_file(file).with_header(header))
+        .map_err(|err| eprintln!("ERROR: could not serve file {file_path}: {err}"))
+}
+
+fn serve_api_search(
+    index: &InvertedIndex,
+    bktree: &BkTree,
+    mut request: Request,
+) -> Result<(), ()> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|err| eprintln!("ERROR: could not read search request body: {err}"))?;
+
+    let prompt = body.chars().collect::<Vec<_>>();
+
+    let search_results = index.search_query(&prompt, bktree);
+
+    let response = SearchResponse {
+        results: search_results
+            .hits
+            .iter()
+            .take(20)
+            .map(|(path, score)| SearchResultEntry {
+                path: path.display().to_string(),
+                score: *score,
+            })
+            .collect(),
+        corrections: search_results
+            .corrections
+            .into_iter()
+            .map(|(term, corrected)| CorrectionEntry { term, corrected })
+            .collect(),
+    };
+
+    let json = serde_json::to_string(&response)
+        .map_err(|err| eprintln!("ERROR: could not serialize search results: {err}"))?;
+
+    let header = Header::from_bytes("Content-Type", "application/json")
+        .expect("static content-type value is always a valid header");
+
+    request
+        .respond(Response::from_string(json).with_header(header))
+        .map_err(|err| eprintln!("ERROR: could not serve search request: {err}"))
+}
+
+fn serve_request(index: &InvertedIndex, bktree: &BkTree, request: Request) -> Result<(), ()> {
+    println!(
+        "INFO: received request! method: {:?}, url: {:?}",
+        request.method(),
+        request.url()
+    );
+
+    match (request.method(), request.url()) {
+        (Method::Post, "/api/search") => serve_api_search(index, bktree, request),
+        (Method::Get, "/" | "/index.html") => {
+            serve_static_file(request, "static/index.html", "text/html; charset=utf-8")
+        }
+        (Method::Get, "/index.js") => {
+            serve_static_file(request, "static/index.js", "text/javascript; charset=utf-8")
+        }
+        _ => serve_404(request),
+    }
+}
+
+pub fn start(address: &str, index: &InvertedIndex) -> Result<(), ()> {
+    let server = tiny_http::Server::http(address).map_err(|err| {
+        eprintln!("ERROR: could not start HTTP server at {address}: {err}");
+    })?;
+
+    let bktree = index.build_bktree();
+
+    println!("INFO: listening at http://{address}/");
+
+    for request in server.incoming_requests() {
+        serve_request(index, &bktree, request).unwrap_or_else(|()| {
+            eprintln!("ERROR: could not serve the request");
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! Porter stemming algorithm (M.F. Porter, 1980), operating on uppercase
+//! ASCII tokens to match [`crate::model::Lexer`]'s output.
+
+fn is_vowel(chars: &[u8], i: usize) -> bool {
+    match chars[i] {
+        b'A' | b'E' | b'I' | b'O' | b'U' => true,
+        b'Y' => i > 0 && !is_vowel(chars, i - 1),
+        _ => false,
+    }
+}
+
+/// Porter's `m`: the number of vowel-consonant sequences in `chars[..end]`.
+fn measure(chars: &[u8], end: usize) -> usize {
+    let mut m = 0;
+    let mut i = 0;
+    while i < end && !is_vowel(chars, i) {
+        i += 1;
+    }
+    while i < end {
+        while i < end && is_vowel(chars, i) {
+            i += 1;
+        }
+        if i >= end {
+            break;
+        }
+        while i < end && !is_vowel(chars, i) {
+            i += 1;
+        }
+        m += 1;
+    }
+    m
+}
+
+fn contains_vowel(chars: &[u8], end: usize) -> bool {
+    (0..end).any(|i| is_vowel(chars, i))
+}
+
+fn ends_double_consonant(chars: &[u8], end: usize) -> bool {
+    end >= 2 && chars[end - 1] == chars[end - 2] && !is_vowel(chars, end - 1)
+}
+
+/// Porter's `*o`: ends cvc, where the final consonant is not W, X or Y.
+fn ends_cvc(chars: &[u8], end: usize) -> bool {
+    end >= 3
+        && !is_vowel(chars, end - 3)
+        && is_vowel(chars, end - 2)
+        && !is_vowel(chars, end - 1)
+        && !matches!(chars[end - 1], b'W' | b'X' | b'Y')
+}
+
+fn ends_with(chars: &[u8], end: usize, suffix: &str) -> bool {
+    let suffix = suffix.as_bytes();
+    end >= suffix.len() && &chars[end - suffix.len()..end] == suffix
+}
+
+fn replace_suffix(chars: &mut Vec<u8>, end: usize, suffix: &str, replacement: &str) -> usize {
+    let new_end = end - suffix.len();
+    chars.truncate(new_end);
+    chars.extend_from_slice(replacement.as_bytes());
+    new_end + replacement.len()
+}
+
+fn step1a(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    if ends_with(chars, end, "SSES") {
+        end = replace_suffix(chars, end, "SSES", "SS");
+    } else if ends_with(chars, end, "IES") {
+        end = replace_suffix(chars, end, "IES", "I");
+    } else if ends_with(chars, end, "SS") {
+        // unchanged
+    } else if ends_with(chars, end, "S") {
+        end = replace_suffix(chars, end, "S", "");
+    }
+    end
+}
+
+fn step1b(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    let applied_ed_or_ing;
+
+    if ends_with(chars, end, "EED") {
+        if measure(chars, end - 3) > 0 {
+            end = replace_suffix(chars, end, "EED", "EE");
+        }
+        return end;
+    } else if ends_with(chars, end, "ED") && contains_vowel(chars, end - 2) {
+        end = replace_suffix(chars, end, "ED", "");
+        applied_ed_or_ing = true;
+    } else if ends_with(chars, end, "ING") && contains_vowel(chars, end - 3) {
+        end = replace_suffix(chars, end, "ING", "");
+        applied_ed_or_ing = true;
+    } else {
+        applied_ed_or_ing = false;
+    }
+
+    if applied_ed_or_ing {
+        if ends_with(chars, end, "AT") {
+            end = replace_suffix(chars, end, "AT", "ATE");
+        } else if ends_with(chars, end, "BL") {
+            end = replace_suffix(chars, end, "BL", "BLE");
+        } else if ends_with(chars, end, "IZ") {
+            end = replace_suffix(chars, end, "IZ", "IZE");
+        } else if ends_double_consonant(chars, end) && !matches!(chars[end - 1], b'L' | b'S' | b'Z')
+        {
+            end -= 1;
+            chars.truncate(end);
+        } else if measure(chars, end) == 1 && ends_cvc(chars, end) {
+            chars.push(b'E');
+            end += 1;
+        }
+    }
+
+    end
+}
+
+fn step1c(chars: &mut [u8], end: usize) -> usize {
+    if ends_with(chars, end, "Y") && contains_vowel(chars, end - 1) {
+        chars[end - 1] = b'I';
+    }
+    end
+}
+
+const STEP2_SUFFIXES: &[(&str, &str)] = &[
+    ("ATIONAL", "ATE"),
+    ("TIONAL", "TION"),
+    ("ENCI", "ENCE"),
+    ("ANCI", "ANCE"),
+    ("IZER", "IZE"),
+    ("ABLI", "ABLE"),
+    ("ALLI", "AL"),
+    ("ENTLI", "ENT"),
+    ("ELI", "E"),
+    ("OUSLI", "OUS"),
+    ("IZATION", "IZE"),
+    ("ATION", "ATE"),
+    ("ATOR", "ATE"),
+    ("ALISM", "AL"),
+    ("IVENESS", "IVE"),
+    ("FULNESS", "FUL"),
+    ("OUSNESS", "OUS"),
+    ("ALITI", "AL"),
+    ("IVITI", "IVE"),
+    ("BILITI", "BLE"),
+];
+
+fn step2(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    for (suffix, replacement) in STEP2_SUFFIXES {
+        if ends_with(chars, end, suffix) && measure(chars, end - suffix.len()) > 0 {
+            end = replace_suffix(chars, end, suffix, replacement);
+            break;
+        }
+    }
+    end
+}
+
+const STEP3_SUFFIXES: &[(&str, &str)] = &[
+    ("ICATE", "IC"),
+    ("ATIVE", ""),
+    ("ALIZE", "AL"),
+    ("ICITI", "IC"),
+    ("ICAL", "IC"),
+    ("FUL", ""),
+    ("NESS", ""),
+];
+
+fn step3(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    for (suffix, replacement) in STEP3_SUFFIXES {
+        if ends_with(chars, end, suffix) && measure(chars, end - suffix.len()) > 0 {
+            end = replace_suffix(chars, end, suffix, replacement);
+            break;
+        }
+    }
+    end
+}
+
+const STEP4_SUFFIXES: &[&str] = &[
+    "AL", "ANCE", "ENCE", "ER", "IC", "ABLE", "IBLE", "ANT", "EMENT", "MENT", "ENT", "ION", "OU",
+    "ISM", "ATE", "ITI", "OUS", "IVE", "IZE",
+];
+
+fn step4(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    for suffix in STEP4_SUFFIXES {
+        if !ends_with(chars, end, suffix) {
+            continue;
+        }
+        let stem_end = end - suffix.len();
+        if *suffix == "ION" && !matches!(chars.get(stem_end.wrapping_sub(1)), Some(b'S' | b'T')) {
+            continue;
+        }
+        if measure(chars, stem_end) > 1 {
+            end = replace_suffix(chars, end, suffix, "");
+        }
+        break;
+    }
+    end
+}
+
+fn step5a(chars: &mut Vec<u8>, mut end: usize) -> usize {
+    if ends_with(chars, end, "E") {
+        let stem_end = end - 1;
+        let m = measure(chars, stem_end);
+        if m > 1 || (m == 1 && !ends_cvc(chars, stem_end)) {
+            end = replace_suffix(chars, end, "E", "");
+        }
+    }
+    end
+}
+
+fn step5b(chars: &mut Vec<u8>, end: usize) -> usize {
+    if ends_with(chars, end, "LL") && measure(chars, end) > 1 {
+        chars.truncate(end - 1);
+        return end - 1;
+    }
+    end
+}
+
+/// Reduces an uppercase ASCII word to its Porter stem. Words shorter than
+/// three letters, or containing non-ASCII-alphabetic characters, are
+/// returned unchanged.
+pub fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return word.to_string();
+    }
+
+    let mut chars = word.as_bytes().to_vec();
+    let mut end = chars.len();
+
+    end = step1a(&mut chars, end);
+    end = step1b(&mut chars, end);
+    end = step1c(&mut chars, end);
+    end = step2(&mut chars, end);
+    end = step3(&mut chars, end);
+    end = step4(&mut chars, end);
+    end = step5a(&mut chars, end);
+    end = step5b(&mut chars, end);
+
+    chars.truncate(end);
+    String::from_utf8(chars).expect("stemmer only touches ASCII bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stem;
+
+    #[test]
+    fn collapses_common_morphological_variants() {
+        assert_eq!(stem("RUNNING"), "RUN");
+        assert_eq!(stem("RUNS"), "RUN");
+        assert_eq!(stem("NATIONAL"), "NATION");
+        assert_eq!(stem("RELATIONAL"), "RELAT");
+        assert_eq!(stem("CARESSES"), "CARESS");
+        assert_eq!(stem("PONIES"), "PONI");
+    }
+
+    #[test]
+    fn leaves_short_or_non_alphabetic_tokens_alone() {
+        assert_eq!(stem("AT"), "AT");
+        assert_eq!(stem("2024"), "2024");
+    }
+}